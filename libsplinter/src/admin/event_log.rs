@@ -0,0 +1,136 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A bounded, per-circuit-management-type ring buffer of emitted circuit-management events,
+//! letting a websocket subscriber that connects after some events have already flowed replay
+//! everything it missed before switching over to live delivery.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// A single circuit-management event, tagged with a monotonically increasing sequence number
+/// that is unique across all management types.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CircuitEvent {
+    pub sequence: u64,
+    pub payload: Vec<u8>,
+}
+
+/// Per-management-type bounded event history, used to replay events to a subscriber that
+/// reconnects with `?since=<seq>`.
+pub struct EventLog {
+    capacity: usize,
+    next_sequence: AtomicU64,
+    buffers: Mutex<HashMap<String, VecDeque<CircuitEvent>>>,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            next_sequence: AtomicU64::new(1),
+            buffers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record an emitted event for `circuit_management_type`, returning its sequence number.
+    pub fn record(&self, circuit_management_type: &str, payload: Vec<u8>) -> u64 {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst);
+
+        if let Ok(mut buffers) = self.buffers.lock() {
+            let buffer = buffers
+                .entry(circuit_management_type.to_string())
+                .or_insert_with(VecDeque::new);
+
+            buffer.push_back(CircuitEvent { sequence, payload });
+            while buffer.len() > self.capacity {
+                buffer.pop_front();
+            }
+        }
+
+        sequence
+    }
+
+    /// All buffered events for `circuit_management_type` with a sequence number greater than
+    /// `since`, in the order they were recorded.
+    pub fn replay_since(&self, circuit_management_type: &str, since: u64) -> Vec<CircuitEvent> {
+        self.buffers
+            .lock()
+            .ok()
+            .and_then(|buffers| buffers.get(circuit_management_type).cloned())
+            .map(|buffer| {
+                buffer
+                    .into_iter()
+                    .filter(|event| event.sequence > since)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Parse the `since` query parameter from a raw HTTP query string (e.g. `since=42`).
+pub fn parse_since(query_string: &str) -> Option<u64> {
+    query_string.split('&').find_map(|pair| {
+        let mut parts = pair.splitn(2, '=');
+        let key = parts.next()?;
+        let value = parts.next()?;
+        if key == "since" {
+            value.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_since_returns_only_newer_events() {
+        let log = EventLog::new(10);
+        log.record("test_app", b"one".to_vec());
+        let second = log.record("test_app", b"two".to_vec());
+        log.record("test_app", b"three".to_vec());
+
+        let replayed = log.replay_since("test_app", second - 1);
+
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[0].payload, b"two".to_vec());
+        assert_eq!(replayed[1].payload, b"three".to_vec());
+    }
+
+    #[test]
+    fn test_event_log_drops_oldest_beyond_capacity() {
+        let log = EventLog::new(2);
+        log.record("test_app", b"one".to_vec());
+        log.record("test_app", b"two".to_vec());
+        log.record("test_app", b"three".to_vec());
+
+        let replayed = log.replay_since("test_app", 0);
+
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[0].payload, b"two".to_vec());
+        assert_eq!(replayed[1].payload, b"three".to_vec());
+    }
+
+    #[test]
+    fn test_parse_since_extracts_value() {
+        assert_eq!(parse_since("since=42"), Some(42));
+        assert_eq!(parse_since("foo=bar&since=7"), Some(7));
+        assert_eq!(parse_since("foo=bar"), None);
+        assert_eq!(parse_since(""), None);
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus-style metrics for circuit-formation health.
+//!
+//! `AdminMetrics` is a registry of counters and gauges that `AdminService` updates as proposals,
+//! votes, and consensus rounds move through the system. `render_prometheus` serializes the
+//! current values in the Prometheus text exposition format for the `GET /admin/metrics` resource.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Default)]
+pub struct AdminMetrics {
+    proposals_received: AtomicU64,
+    proposals_accepted: AtomicU64,
+    proposals_rejected: AtomicU64,
+    votes_tallied: AtomicU64,
+    pending_proposals: AtomicU64,
+    subscribers_by_type: Mutex<HashMap<String, u64>>,
+    consensus_round_latency: Mutex<LatencyHistogram>,
+}
+
+impl AdminMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn incr_proposals_received(&self) {
+        self.proposals_received.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn incr_proposals_accepted(&self) {
+        self.proposals_accepted.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn incr_proposals_rejected(&self) {
+        self.proposals_rejected.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn incr_votes_tallied(&self) {
+        self.votes_tallied.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn set_pending_proposals(&self, count: u64) {
+        self.pending_proposals.store(count, Ordering::SeqCst);
+    }
+
+    pub fn set_subscriber_count(&self, circuit_management_type: &str, count: u64) {
+        if let Ok(mut subscribers) = self.subscribers_by_type.lock() {
+            subscribers.insert(circuit_management_type.to_string(), count);
+        }
+    }
+
+    pub fn observe_consensus_round_latency(&self, latency: Duration) {
+        if let Ok(mut histogram) = self.consensus_round_latency.lock() {
+            histogram.observe(latency);
+        }
+    }
+
+    /// Serialize the current state of the registry in the Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        write_counter(
+            &mut out,
+            "splinter_admin_proposals_received_total",
+            "Total number of circuit proposals received by the admin service.",
+            self.proposals_received.load(Ordering::SeqCst),
+        );
+        write_counter(
+            &mut out,
+            "splinter_admin_proposals_accepted_total",
+            "Total number of circuit proposals accepted by consensus.",
+            self.proposals_accepted.load(Ordering::SeqCst),
+        );
+        write_counter(
+            &mut out,
+            "splinter_admin_proposals_rejected_total",
+            "Total number of circuit proposals rejected by consensus.",
+            self.proposals_rejected.load(Ordering::SeqCst),
+        );
+        write_counter(
+            &mut out,
+            "splinter_admin_votes_tallied_total",
+            "Total number of circuit proposal votes tallied.",
+            self.votes_tallied.load(Ordering::SeqCst),
+        );
+
+        write_gauge(
+            &mut out,
+            "splinter_admin_pending_proposals",
+            "Number of circuit proposals awaiting consensus.",
+            self.pending_proposals.load(Ordering::SeqCst),
+        );
+
+        if let Ok(subscribers) = self.subscribers_by_type.lock() {
+            writeln!(
+                out,
+                "# HELP splinter_admin_ws_subscribers Number of active websocket subscribers, by circuit management type."
+            )
+            .expect("Unable to write to string");
+            writeln!(out, "# TYPE splinter_admin_ws_subscribers gauge")
+                .expect("Unable to write to string");
+            for (circuit_management_type, count) in subscribers.iter() {
+                writeln!(
+                    out,
+                    "splinter_admin_ws_subscribers{{circuit_management_type=\"{}\"}} {}",
+                    circuit_management_type, count
+                )
+                .expect("Unable to write to string");
+            }
+        }
+
+        if let Ok(histogram) = self.consensus_round_latency.lock() {
+            histogram.render_prometheus(&mut out);
+        }
+
+        out
+    }
+}
+
+fn write_counter(out: &mut String, name: &str, help: &str, value: u64) {
+    writeln!(out, "# HELP {} {}", name, help).expect("Unable to write to string");
+    writeln!(out, "# TYPE {} counter", name).expect("Unable to write to string");
+    writeln!(out, "{} {}", name, value).expect("Unable to write to string");
+}
+
+fn write_gauge(out: &mut String, name: &str, help: &str, value: u64) {
+    writeln!(out, "# HELP {} {}", name, help).expect("Unable to write to string");
+    writeln!(out, "# TYPE {} gauge", name).expect("Unable to write to string");
+    writeln!(out, "{} {}", name, value).expect("Unable to write to string");
+}
+
+/// A minimal histogram tracking the count and total duration of consensus rounds, rendered as
+/// the `_sum`/`_count` pair Prometheus clients use to derive an average.
+#[derive(Default)]
+struct LatencyHistogram {
+    count: u64,
+    sum_millis: u64,
+}
+
+impl LatencyHistogram {
+    fn observe(&mut self, latency: Duration) {
+        self.count += 1;
+        self.sum_millis += latency.as_millis() as u64;
+    }
+
+    fn render_prometheus(&self, out: &mut String) {
+        writeln!(
+            out,
+            "# HELP splinter_admin_consensus_round_latency_ms_sum Sum of observed consensus round latencies, in milliseconds."
+        )
+        .expect("Unable to write to string");
+        writeln!(
+            out,
+            "# TYPE splinter_admin_consensus_round_latency_ms_sum counter"
+        )
+        .expect("Unable to write to string");
+        writeln!(
+            out,
+            "splinter_admin_consensus_round_latency_ms_sum {}",
+            self.sum_millis
+        )
+        .expect("Unable to write to string");
+
+        writeln!(
+            out,
+            "# HELP splinter_admin_consensus_round_latency_ms_count Count of observed consensus rounds."
+        )
+        .expect("Unable to write to string");
+        writeln!(
+            out,
+            "# TYPE splinter_admin_consensus_round_latency_ms_count counter"
+        )
+        .expect("Unable to write to string");
+        writeln!(
+            out,
+            "splinter_admin_consensus_round_latency_ms_count {}",
+            self.count
+        )
+        .expect("Unable to write to string");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus_includes_all_metrics() {
+        let metrics = AdminMetrics::new();
+        metrics.incr_proposals_received();
+        metrics.incr_proposals_accepted();
+        metrics.incr_votes_tallied();
+        metrics.set_pending_proposals(2);
+        metrics.set_subscriber_count("test_app", 3);
+        metrics.observe_consensus_round_latency(Duration::from_millis(150));
+
+        let rendered = metrics.render_prometheus();
+
+        assert!(rendered.contains("splinter_admin_proposals_received_total 1"));
+        assert!(rendered.contains("splinter_admin_proposals_accepted_total 1"));
+        assert!(rendered.contains("splinter_admin_votes_tallied_total 1"));
+        assert!(rendered.contains("splinter_admin_pending_proposals 2"));
+        assert!(rendered.contains("splinter_admin_ws_subscribers{circuit_management_type=\"test_app\"} 3"));
+        assert!(rendered.contains("splinter_admin_consensus_round_latency_ms_sum 150"));
+        assert!(rendered.contains("splinter_admin_consensus_round_latency_ms_count 1"));
+    }
+}
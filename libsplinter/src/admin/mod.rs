@@ -14,11 +14,19 @@
 
 mod consensus;
 mod error;
+mod event_log;
+mod metrics;
 pub mod messages;
+mod reconnect;
 mod shared;
+mod shutdown;
+mod signing;
 
 use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
 
 use openssl::hash::{hash, MessageDigest};
 use protobuf::{self, Message};
@@ -32,7 +40,9 @@ use crate::network::{
     peer::PeerConnector,
 };
 use crate::orchestrator::ServiceOrchestrator;
-use crate::protos::admin::{AdminMessage, AdminMessage_Type};
+use crate::protos::admin::{
+    AdminMessage, AdminMessage_Type, CircuitManagementPayload, CircuitProposalVote_Vote,
+};
 use crate::rest_api::{Method, Request, Resource, RestResourceProvider};
 use crate::service::{
     error::{ServiceDestroyError, ServiceError, ServiceStartError, ServiceStopError},
@@ -43,11 +53,30 @@ use self::consensus::AdminConsensusManager;
 use self::error::{AdminError, Sha256Error};
 use self::messages::{from_payload, CircuitProposalVote, CreateCircuit};
 use self::shared::AdminServiceShared;
+use self::signing::verify;
+
+pub use self::event_log::{CircuitEvent, EventLog};
+pub use self::metrics::AdminMetrics;
+pub use self::reconnect::{ConnectionPool, ReconnectManager, ReconnectPolicy};
+pub use self::shutdown::{ServiceState, Shutdown, ShutdownTrigger};
+pub use self::signing::{KeyRegistry, SigningKeyPair};
+
+/// How long `AdminService::stop` waits for pending consensus proposals to resolve before giving
+/// up and shutting down anyway.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many events each circuit management type's replay buffer retains.
+const EVENT_LOG_CAPACITY: usize = 256;
 
 pub struct AdminService {
     service_id: String,
     admin_service_shared: Arc<Mutex<AdminServiceShared>>,
-    consensus: Option<AdminConsensusManager>,
+    consensus: Arc<Mutex<Option<AdminConsensusManager>>>,
+    event_log: Arc<EventLog>,
+    reconnect_thread: Option<thread::JoinHandle<()>>,
+    reconnect_shutdown: Arc<AtomicBool>,
+    shutdown: Arc<Shutdown>,
+    shutdown_trigger: ShutdownTrigger,
 }
 
 impl AdminService {
@@ -58,6 +87,16 @@ impl AdminService {
         authorization_inquistor: Box<dyn AuthorizationInquisitor>,
         splinter_state: Arc<RwLock<SplinterState>>,
     ) -> Result<Self, ServiceError> {
+        // Each node signs its own outgoing circuit management payloads with this keypair, and
+        // registers the public half under its own node id so that payloads it loops back to
+        // itself (and any node that already trusts this key out of band) verify correctly.
+        let signing_key = Arc::new(
+            SigningKeyPair::generate().map_err(|err| ServiceError::UnableToCreate(Box::new(err)))?,
+        );
+        let own_public_key = signing_key
+            .public_key_bytes()
+            .map_err(|err| ServiceError::UnableToCreate(Box::new(err)))?;
+
         let new_service = Self {
             service_id: admin_service_id(node_id),
             admin_service_shared: Arc::new(Mutex::new(AdminServiceShared::new(
@@ -66,10 +105,27 @@ impl AdminService {
                 peer_connector,
                 authorization_inquistor,
                 splinter_state,
+                signing_key,
             ))),
-            consensus: None,
+            consensus: Arc::new(Mutex::new(None)),
+            event_log: Arc::new(EventLog::new(EVENT_LOG_CAPACITY)),
+            reconnect_thread: None,
+            reconnect_shutdown: Arc::new(AtomicBool::new(false)),
+            shutdown: Arc::new(Shutdown::new(SHUTDOWN_DRAIN_TIMEOUT)),
+            shutdown_trigger: ShutdownTrigger::new(),
         };
 
+        new_service
+            .admin_service_shared
+            .lock()
+            .map_err(|_| {
+                ServiceError::PoisonedLock(
+                    "The lock was poisoned while creating the service".into(),
+                )
+            })?
+            .key_registry()
+            .register_key(node_id, own_public_key);
+
         let auth_callback_shared = Arc::clone(&new_service.admin_service_shared);
 
         new_service
@@ -83,14 +139,30 @@ impl AdminService {
             .auth_inquisitor()
             .register_callback(Box::new(
                 move |peer_id: &str, state: PeerAuthorizationState| {
-                    auth_callback_shared
-                        .lock()
-                        .map_err(|_| {
-                            AuthorizationCallbackError(
-                                "admin service shared lock was poisoned".into(),
-                            )
-                        })?
-                        .on_authorization_change(peer_id, state);
+                    let mut shared = auth_callback_shared.lock().map_err(|_| {
+                        AuthorizationCallbackError("admin service shared lock was poisoned".into())
+                    })?;
+
+                    shared.on_authorization_change(peer_id, state);
+
+                    // The network layer's authorization handshake is the trust root for a
+                    // peer's signing key: it authenticates `peer_id` independently of anything
+                    // an (unverified, in-flight) circuit management payload claims about itself.
+                    // Registering the key here, rather than out of the payload currently being
+                    // verified, is what makes `verify_payload_signature` an actual authentication
+                    // check instead of a no-op against an attacker-supplied key.
+                    if state == PeerAuthorizationState::Authorized {
+                        if let Some(public_key) = shared.auth_inquisitor().peer_public_key(peer_id)
+                        {
+                            shared.key_registry().register_key(peer_id, public_key);
+                        }
+                    }
+
+                    if let Some(endpoint) = shared.member_endpoint(peer_id) {
+                        shared
+                            .reconnect_manager()
+                            .handle_authorization_change(peer_id, &endpoint, state);
+                    }
 
                     Ok(())
                 },
@@ -99,6 +171,13 @@ impl AdminService {
 
         Ok(new_service)
     }
+
+    /// A cloneable handle that a process signal handler (e.g. SIGINT) can use to request an
+    /// orderly shutdown of this service; the caller driving the service's lifecycle is
+    /// responsible for polling `ShutdownTrigger::is_triggered` and calling `Service::stop`.
+    pub fn shutdown_trigger(&self) -> ShutdownTrigger {
+        self.shutdown_trigger.clone()
+    }
 }
 
 impl Service for AdminService {
@@ -114,8 +193,19 @@ impl Service for AdminService {
         &mut self,
         service_registry: &dyn ServiceNetworkRegistry,
     ) -> Result<(), ServiceStartError> {
-        if self.consensus.is_some() {
-            return Err(ServiceStartError::AlreadyStarted);
+        // A prior `stop` leaves this at `Stopped`; starting again must accept new work.
+        self.shutdown.mark_running();
+
+        // `handle_message` and the vote route always lock `admin_service_shared` before
+        // `consensus` (when they need both); never hold both locks at once here, so this
+        // function can't complete the opposite order and deadlock against them.
+        {
+            let consensus_guard = self.consensus.lock().map_err(|_| {
+                ServiceStartError::PoisonedLock("the admin consensus lock was poisoned".into())
+            })?;
+            if consensus_guard.is_some() {
+                return Err(ServiceStartError::AlreadyStarted);
+            }
         }
 
         let network_sender = service_registry.connect(&self.service_id)?;
@@ -129,10 +219,64 @@ impl Service for AdminService {
         }
 
         // Setup consensus
-        self.consensus = Some(
-            AdminConsensusManager::new(self.service_id().into(), self.admin_service_shared.clone())
+        {
+            let mut consensus_guard = self.consensus.lock().map_err(|_| {
+                ServiceStartError::PoisonedLock("the admin consensus lock was poisoned".into())
+            })?;
+            *consensus_guard = Some(
+                AdminConsensusManager::new(
+                    self.service_id().into(),
+                    self.admin_service_shared.clone(),
+                )
                 .map_err(|err| ServiceStartError::Internal(Box::new(err)))?,
-        );
+            );
+        }
+
+        self.reconnect_shutdown.store(false, Ordering::SeqCst);
+        let reconnect_shared = Arc::clone(&self.admin_service_shared);
+        let reconnect_shutdown = Arc::clone(&self.reconnect_shutdown);
+        self.reconnect_thread = Some(thread::spawn(move || {
+            while !reconnect_shutdown.load(Ordering::SeqCst) {
+                // Collect due peers and a cheap connector handle while holding the lock, then
+                // release it before dialing: `connect_peer` is blocking network I/O, and holding
+                // this lock across it would serialize every REST route and `handle_message` call
+                // behind a slow or hung connection attempt.
+                let (due, peer_connector) = match reconnect_shared.lock() {
+                    Ok(mut shared) => {
+                        let manager = shared.reconnect_manager();
+                        (manager.due_peers(), manager.peer_connector())
+                    }
+                    Err(_) => break,
+                };
+
+                for (node_id, endpoint) in due {
+                    let result = peer_connector
+                        .connect_peer(&node_id, &endpoint)
+                        .map_err(|_| ());
+
+                    let exhausted = match reconnect_shared.lock() {
+                        Ok(mut shared) => shared
+                            .reconnect_manager()
+                            .complete_attempt(&node_id, result),
+                        Err(_) => break,
+                    };
+
+                    if let Some(node_id) = exhausted {
+                        error!(
+                            "Exhausted reconnection attempts for peer {}; failing proposals \
+                             waiting on it",
+                            node_id
+                        );
+                        if let Ok(mut shared) = reconnect_shared.lock() {
+                            shared.fail_proposals_waiting_on(&node_id);
+                        }
+                    }
+                }
+
+                thread::sleep(Duration::from_millis(250));
+            }
+        }));
+
         Ok(())
     }
 
@@ -140,14 +284,49 @@ impl Service for AdminService {
         &mut self,
         service_registry: &dyn ServiceNetworkRegistry,
     ) -> Result<(), ServiceStopError> {
-        service_registry.disconnect(&self.service_id)?;
+        // Enter the draining state first: REST routes start returning 503 for new proposals
+        // (`/admin/circuit`, the websocket registration route), but votes keep flowing so
+        // anything already pending gets a chance to resolve below.
+        self.shutdown.begin_draining();
+
+        self.reconnect_shutdown.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.reconnect_thread.take() {
+            let _ = handle.join();
+        }
+
+        let drain_shared = Arc::clone(&self.admin_service_shared);
+        let drained = self.shutdown.wait_for_drain(|| {
+            drain_shared
+                .lock()
+                .map(|shared| shared.pending_proposal_count())
+                .unwrap_or(0)
+        });
+        if !drained {
+            error!(
+                "Shutdown drain timeout elapsed with circuit proposals still pending; \
+                 shutting down anyway"
+            );
+        }
 
         let mut admin_service_shared = self.admin_service_shared.lock().map_err(|_| {
             ServiceStopError::PoisonedLock("the admin shared lock was poisoned".into())
         })?;
 
+        // Close websocket subscribers with a proper close frame before tearing down consensus.
+        // Capture which circuit management types had subscribers first so the gauge can be
+        // zeroed for each afterward; `close_subscribers` does not report that itself.
+        let circuit_management_types = admin_service_shared.subscribed_circuit_management_types();
+        admin_service_shared.close_subscribers();
+        for circuit_management_type in &circuit_management_types {
+            admin_service_shared
+                .metrics()
+                .set_subscriber_count(circuit_management_type, 0);
+        }
+
         // Shutdown consensus
         self.consensus
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("the admin consensus lock was poisoned".into()))?
             .take()
             .ok_or_else(|| ServiceStopError::NotStarted)?
             .shutdown()
@@ -158,13 +337,21 @@ impl Service for AdminService {
 
         admin_service_shared.set_network_sender(None);
 
+        self.shutdown.mark_stopped();
+
         info!("Admin service stopped and disconnected");
 
         Ok(())
     }
 
     fn destroy(self: Box<Self>) -> Result<(), ServiceDestroyError> {
-        if self.consensus.is_some() {
+        let started = self
+            .consensus
+            .lock()
+            .map_err(|_| ServiceDestroyError::PoisonedLock("the admin consensus lock was poisoned".into()))?
+            .is_some();
+
+        if started {
             Err(ServiceDestroyError::NotStopped)
         } else {
             Ok(())
@@ -180,17 +367,51 @@ impl Service for AdminService {
             .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
         debug!("received admin message {:?}", admin_message);
         match admin_message.get_message_type() {
-            AdminMessage_Type::CONSENSUS_MESSAGE => self
-                .consensus
-                .as_ref()
-                .ok_or_else(|| ServiceError::NotStarted)?
-                .handle_message(admin_message.get_consensus_message())
-                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err))),
+            AdminMessage_Type::CONSENSUS_MESSAGE => {
+                // Scoped so the consensus lock is released before `admin_service_shared` is ever
+                // locked below, matching the global "shared before consensus" order used by
+                // `start`, `handle_message`'s `PROPOSED_CIRCUIT` branch, and the vote route.
+                let resolved = {
+                    let consensus_guard = self.consensus.lock().map_err(|_| {
+                        ServiceError::PoisonedLock("the admin consensus lock was poisoned".into())
+                    })?;
+                    consensus_guard
+                        .as_ref()
+                        .ok_or_else(|| ServiceError::NotStarted)?
+                        .handle_message(admin_message.get_consensus_message())
+                        .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
+                };
+
+                // A proposal can also be finalized purely by consensus messages exchanged
+                // between nodes, without this node's own vote ever resolving the quorum tally in
+                // `make_vote_route`; count and reflect that resolution here so it isn't missed.
+                if let Some((proposal_id, accepted)) = resolved {
+                    let mut admin_service_shared = self.admin_service_shared.lock().map_err(|_| {
+                        ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                    })?;
+                    if accepted {
+                        admin_service_shared.metrics().incr_proposals_accepted();
+                    } else {
+                        admin_service_shared.metrics().incr_proposals_rejected();
+                    }
+                    admin_service_shared
+                        .metrics()
+                        .set_pending_proposals(admin_service_shared.pending_proposal_count() as u64);
+                    debug!(
+                        "Circuit proposal {:?} resolved via consensus message (accepted={})",
+                        proposal_id, accepted
+                    );
+                }
+
+                Ok(())
+            }
             AdminMessage_Type::PROPOSED_CIRCUIT => {
                 let proposed_circuit = admin_message.get_proposed_circuit();
 
                 let expected_hash = proposed_circuit.get_expected_hash().to_vec();
                 let circuit_payload = proposed_circuit.get_circuit_payload();
+                // The public keys of the nodes whose signatures consensus must eventually
+                // collect before the proposal can be confirmed.
                 let required_verifiers = proposed_circuit.get_required_verifiers();
                 let mut proposal = Proposal::default();
 
@@ -201,16 +422,46 @@ impl Service for AdminService {
                 proposal.summary = expected_hash;
                 proposal.consensus_data = required_verifiers.to_vec();
 
+                let payload: CircuitManagementPayload = protobuf::parse_from_bytes(circuit_payload)
+                    .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
                 let mut admin_service_shared = self.admin_service_shared.lock().map_err(|_| {
                     ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
                 })?;
 
+                // Deliberately not `register_member_keys` here: this payload's own signature is
+                // what's about to be checked, so any key it supplies for itself is unverified and
+                // must not be trusted. The requester's key must already be registered from a
+                // source independent of this payload (network-layer peer authorization, or this
+                // node's own earlier `create_circuit` call if it is the proposer).
+                let _requester_node_id =
+                    verify_payload_signature(&payload, admin_service_shared.key_registry())?;
+
+                let circuit_management_type = payload
+                    .get_circuit_create_request()
+                    .get_circuit()
+                    .get_circuit_management_type()
+                    .to_string();
+                self.event_log
+                    .record(&circuit_management_type, circuit_payload.clone());
+
                 admin_service_shared.add_pending_consesus_proposal(
                     proposal.id.clone(),
                     (proposal.clone(), circuit_payload.clone()),
                 );
 
-                self.consensus
+                // Proposals are counted here, at network receipt, so a node's own locally
+                // submitted proposal is counted exactly once rather than once on submission and
+                // again when it loops back through this handler.
+                admin_service_shared.metrics().incr_proposals_received();
+                admin_service_shared
+                    .metrics()
+                    .set_pending_proposals(admin_service_shared.pending_proposal_count() as u64);
+
+                let consensus_guard = self.consensus.lock().map_err(|_| {
+                    ServiceError::PoisonedLock("the admin consensus lock was poisoned".into())
+                })?;
+                consensus_guard
                     .as_ref()
                     .ok_or_else(|| ServiceError::NotStarted)?
                     .send_update(ProposalUpdate::ProposalReceived(
@@ -230,6 +481,70 @@ pub fn admin_service_id(node_id: &str) -> String {
     format!("admin::{}", node_id)
 }
 
+/// Verify that `payload`'s detached signature was produced by its purported requester, rejecting
+/// payloads from unregistered signers or with a signature that does not cover the full payload.
+/// On success, returns the verified requester's node id.
+fn verify_payload_signature(
+    payload: &CircuitManagementPayload,
+    key_registry: &KeyRegistry,
+) -> Result<String, ServiceError> {
+    let header: crate::protos::admin::CircuitManagementPayload_Header =
+        protobuf::parse_from_bytes(payload.get_header())
+            .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
+    let requester_node_id = header.get_requester_node_id().to_string();
+
+    let public_key = key_registry
+        .public_key_for_node(&requester_node_id)
+        .ok_or_else(|| {
+            ServiceError::InvalidMessageFormat(Box::new(AdminError::UnknownSigner(
+                requester_node_id.clone(),
+            )))
+        })?;
+
+    // Verify over the full canonical payload (header and circuit_create_request/
+    // circuit_proposal_vote body), not just the header, so a compromised relay cannot swap in a
+    // different body while leaving the header's signature intact.
+    let mut unsigned_payload = payload.clone();
+    unsigned_payload.clear_signature();
+    let canonical_bytes = unsigned_payload
+        .write_to_bytes()
+        .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
+    let verified = verify(&public_key, &canonical_bytes, payload.get_signature())
+        .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
+    if verified {
+        Ok(requester_node_id)
+    } else {
+        Err(ServiceError::InvalidMessageFormat(Box::new(
+            AdminError::InvalidSignature(requester_node_id),
+        )))
+    }
+}
+
+/// Register the public key each member of `circuit` has declared for itself. Only safe to call
+/// with a circuit definition this node authored itself (i.e. from `create_circuit`): the proposer
+/// is the trust root for its own locally-submitted circuit. Must never be called with a circuit
+/// definition taken from an inbound, not-yet-verified payload, since that would let the payload
+/// vouch for its own signer's key.
+fn register_member_keys(key_registry: &KeyRegistry, circuit: &crate::protos::admin::Circuit) {
+    for member in circuit.get_members() {
+        key_registry.register_key(member.get_node_id(), member.get_admin_public_key().to_vec());
+    }
+}
+
+/// The result of tallying a single vote against a pending proposal's required verifiers.
+pub enum VoteRecordOutcome {
+    /// The vote was recorded, but quorum has not yet been reached.
+    Recorded,
+    /// This voter already cast a vote for this proposal.
+    DuplicateVote,
+    /// This vote completed the tally over the required verifiers; `accepted` is `true` when the
+    /// circuit should be committed and `false` when it should be discarded.
+    QuorumReached { proposal_id: Vec<u8>, accepted: bool },
+}
+
 pub fn sha256<T>(message: &T) -> Result<String, Sha256Error>
 where
     T: Message,
@@ -254,49 +569,204 @@ fn to_hex(bytes: &[u8]) -> String {
 impl RestResourceProvider for AdminService {
     fn resources(&self) -> Vec<Resource> {
         vec![
-            make_create_circuit_route(self.admin_service_shared.clone()),
-            make_application_handler_registration_route(self.admin_service_shared.clone()),
-            make_vote_route(self.admin_service_shared.clone()),
+            make_create_circuit_route(self.admin_service_shared.clone(), self.shutdown.clone()),
+            make_application_handler_registration_route(
+                self.admin_service_shared.clone(),
+                self.event_log.clone(),
+                self.shutdown.clone(),
+            ),
+            make_vote_route(
+                self.admin_service_shared.clone(),
+                self.consensus.clone(),
+                self.shutdown.clone(),
+            ),
+            make_metrics_route(self.admin_service_shared.clone()),
         ]
     }
 }
 
-fn make_create_circuit_route(shared: Arc<Mutex<AdminServiceShared>>) -> Resource {
+fn make_create_circuit_route(
+    shared: Arc<Mutex<AdminServiceShared>>,
+    shutdown: Arc<Shutdown>,
+) -> Resource {
     Resource::new(Method::Post, "/admin/circuit", move |request, payload| {
+        if !shutdown.is_accepting_new_work() {
+            return Box::new(HttpResponse::ServiceUnavailable().finish().into_future());
+        }
         create_circuit(request, payload, shared.clone())
     })
 }
 
-fn make_vote_route(shared: Arc<Mutex<AdminServiceShared>>) -> Resource {
+fn make_vote_route(
+    shared: Arc<Mutex<AdminServiceShared>>,
+    consensus: Arc<Mutex<Option<AdminConsensusManager>>>,
+    shutdown: Arc<Shutdown>,
+) -> Resource {
     Resource::new(Method::Post, "/admin/vote", move |_, payload| {
+        // A vote only ever resolves a proposal that is already pending; it never admits new
+        // work. Gating on `is_resolving_pending_work` (rather than `is_accepting_new_work`) keeps
+        // votes flowing while `Draining`, since a vote can be what lets `stop`'s drain wait
+        // observe `pending_proposal_count` reach zero.
+        if !shutdown.is_resolving_pending_work() {
+            return Box::new(HttpResponse::ServiceUnavailable().finish().into_future());
+        }
+
+        let shared = shared.clone();
+        let consensus = consensus.clone();
         Box::new(
-            from_payload::<CircuitProposalVote>(payload).and_then(|vote| {
+            from_payload::<CircuitProposalVote>(payload).and_then(move |vote| {
                 debug!("Received vote {:#?}", vote);
-                HttpResponse::Accepted().finish().into_future()
+
+                let payload = match vote.into_proto() {
+                    Ok(payload) => payload,
+                    Err(_) => return Ok(HttpResponse::BadRequest().finish()),
+                };
+                let circuit_proposal_vote = payload.get_circuit_proposal_vote();
+                let circuit_id = circuit_proposal_vote.get_circuit_id().to_string();
+                let accept = circuit_proposal_vote.get_vote() == CircuitProposalVote_Vote::ACCEPT;
+
+                let mut shared = match shared.lock() {
+                    Ok(shared) => shared,
+                    Err(_) => return Ok(HttpResponse::InternalServerError().finish()),
+                };
+
+                let voter_node_id = match verify_payload_signature(&payload, shared.key_registry())
+                {
+                    Ok(node_id) => node_id,
+                    Err(_) => return Ok(HttpResponse::BadRequest().finish()),
+                };
+
+                // Only a signature from one of the proposal's required verifiers counts toward
+                // the tally, so a proposal is confirmed only once every required verifier has
+                // cast a valid, signed vote.
+                if !shared.is_required_verifier(&circuit_id, &voter_node_id) {
+                    debug!(
+                        "Rejecting vote from {} for {}: not a required verifier for this proposal",
+                        voter_node_id, circuit_id
+                    );
+                    return Ok(HttpResponse::BadRequest().finish());
+                }
+
+                match shared.record_vote(&circuit_id, &voter_node_id, accept) {
+                    Err(AdminError::UnknownProposal(_)) => Ok(HttpResponse::NotFound().finish()),
+                    Err(err) => {
+                        error!(
+                            "Unable to record vote from {} for circuit {}: {}",
+                            voter_node_id, circuit_id, err
+                        );
+                        Ok(HttpResponse::InternalServerError().finish())
+                    }
+                    Ok(VoteRecordOutcome::DuplicateVote) => Ok(HttpResponse::Conflict().finish()),
+                    Ok(VoteRecordOutcome::Recorded) => {
+                        shared.metrics().incr_votes_tallied();
+                        Ok(HttpResponse::Accepted().finish())
+                    }
+                    Ok(VoteRecordOutcome::QuorumReached {
+                        proposal_id,
+                        accepted,
+                    }) => {
+                        shared.metrics().incr_votes_tallied();
+
+                        // The round ends here, so this is where its latency is observed: the
+                        // time from this node first receiving the proposal to the last required
+                        // verifier's vote completing the tally.
+                        if let Some(received_at) = shared.proposal_received_at(&proposal_id) {
+                            shared
+                                .metrics()
+                                .observe_consensus_round_latency(received_at.elapsed());
+                        }
+
+                        if let Ok(consensus_guard) = consensus.lock() {
+                            if let Some(consensus) = consensus_guard.as_ref() {
+                                let result = if accepted {
+                                    shared.metrics().incr_proposals_accepted();
+                                    consensus.accept_proposal(&proposal_id)
+                                } else {
+                                    shared.metrics().incr_proposals_rejected();
+                                    consensus.reject_proposal(&proposal_id)
+                                };
+
+                                if let Err(err) = result {
+                                    error!(
+                                        "Unable to drive consensus update for circuit {}: {}",
+                                        circuit_id, err
+                                    );
+                                }
+                            }
+                        }
+
+                        // The proposal this vote just resolved is no longer pending; reflect
+                        // that immediately so the gauge tracks "awaiting consensus", not
+                        // "total ever received".
+                        shared
+                            .metrics()
+                            .set_pending_proposals(shared.pending_proposal_count() as u64);
+
+                        Ok(HttpResponse::Accepted().finish())
+                    }
+                }
             }),
         )
     })
 }
 
-fn make_application_handler_registration_route(shared: Arc<Mutex<AdminServiceShared>>) -> Resource {
+fn make_metrics_route(shared: Arc<Mutex<AdminServiceShared>>) -> Resource {
+    Resource::new(Method::Get, "/admin/metrics", move |_, _| {
+        Box::new(match shared.lock() {
+            Ok(shared) => HttpResponse::Ok()
+                .content_type("text/plain; version=0.0.4")
+                .body(shared.metrics().render_prometheus())
+                .into_future(),
+            Err(_) => HttpResponse::InternalServerError().finish().into_future(),
+        })
+    })
+}
+
+fn make_application_handler_registration_route(
+    shared: Arc<Mutex<AdminServiceShared>>,
+    event_log: Arc<EventLog>,
+    shutdown: Arc<Shutdown>,
+) -> Resource {
     Resource::new(
         Method::Get,
         "/ws/admin/register/{type}",
         move |request, payload| {
+            if !shutdown.is_accepting_new_work() {
+                return Box::new(HttpResponse::ServiceUnavailable().finish().into_future());
+            }
+
             let circuit_management_type = if let Some(t) = request.match_info().get("type") {
                 t.to_string()
             } else {
                 return Box::new(HttpResponse::BadRequest().finish().into_future());
             };
+            let since = event_log::parse_since(request.query_string());
 
             let unlocked_shared = shared.lock();
 
             match unlocked_shared {
                 Ok(mut shared) => {
+                    // Replay the backlog and register the subscriber under the same
+                    // `admin_service_shared` lock guard `handle_message` holds while appending to
+                    // `event_log`, so no event can be recorded between the snapshot below and
+                    // this subscriber going live: it either lands in `backlog` (recorded first)
+                    // or is delivered live once subscribed (recorded after), never neither.
+                    let backlog = since
+                        .map(|since| event_log.replay_since(&circuit_management_type, since))
+                        .unwrap_or_default();
+
                     let request = Request::from((request, payload));
                     debug!("circuit management type {}", circuit_management_type);
-                    match shared.add_subscriber(circuit_management_type, request) {
+                    match shared.add_subscriber_since(
+                        circuit_management_type.clone(),
+                        request,
+                        backlog,
+                    ) {
                         Ok(res) => {
+                            shared.metrics().set_subscriber_count(
+                                &circuit_management_type,
+                                shared.subscriber_count(&circuit_management_type) as u64,
+                            );
                             debug!("Websocket response: {:?}", res);
                             Box::new(res.into_future())
                         }
@@ -329,10 +799,14 @@ fn create_circuit(
             let circuit = circuit_create_request.take_circuit();
             let circuit_id = circuit.circuit_id.clone();
             let mut shared = shared.lock().expect("the admin state lock was poisoned");
+            register_member_keys(shared.key_registry(), &circuit);
             if let Err(err) = shared.propose_circuit(circuit) {
                 error!("Unable to submit circuit {} proposal: {}", circuit_id, err);
                 Ok(HttpResponse::BadRequest().finish())
             } else {
+                // Not counted here: `incr_proposals_received` is tallied once, at network
+                // receipt in `handle_message`, to avoid double-counting this node's own
+                // proposal when it is broadcast back to itself as a circuit member.
                 debug!("Circuit {} proposed", circuit_id);
                 Ok(HttpResponse::Accepted().finish())
             }
@@ -624,6 +1098,10 @@ mod tests {
             true
         }
 
+        fn peer_public_key(&self, _: &str) -> Option<Vec<u8>> {
+            None
+        }
+
         fn register_callback(
             &self,
             _: Box<dyn AuthorizationCallback>,
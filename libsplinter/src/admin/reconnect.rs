@@ -0,0 +1,323 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exponential-backoff reconnection for circuit members that are not yet peered, plus a small
+//! bounded pool of already-established member connections so repeated proposals to the same
+//! members reuse a connection instead of reconnecting.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::network::auth::PeerAuthorizationState;
+use crate::network::peer::PeerConnector;
+use crate::transport::Connection;
+
+/// Governs how aggressively an unpeered member is retried: `delay = min(base * 2^attempt, cap)`,
+/// plus jitter in `[0, delay / 2)`, giving up after `max_attempts`.
+#[derive(Clone, Copy, Debug)]
+pub struct ReconnectPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub max_attempts: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_attempts: 10,
+        }
+    }
+}
+
+/// Compute the backoff delay for the given attempt number (0-indexed), including jitter.
+///
+/// `jitter_seed` is mixed into the jitter calculation so callers that already have a value that
+/// varies per peer and per attempt at hand (e.g. from `jitter_seed(node_id, attempt)`) can avoid
+/// pulling in a dedicated RNG dependency just for this.
+pub fn compute_delay(policy: &ReconnectPolicy, attempt: u32, jitter_seed: u64) -> Duration {
+    let exponent = attempt.min(32);
+    let multiplier = 1u64.checked_shl(exponent).unwrap_or(u64::MAX);
+    let uncapped = policy.base_delay.as_millis() as u64;
+    let scaled = uncapped.saturating_mul(multiplier);
+    let capped = scaled.min(policy.max_delay.as_millis() as u64);
+
+    let window = capped / 2;
+    let jitter = if window == 0 {
+        0
+    } else {
+        cheap_jitter(jitter_seed) % window
+    };
+
+    Duration::from_millis(capped + jitter)
+}
+
+/// A per-peer, per-attempt jitter seed. `Instant::now().elapsed()` read immediately after the
+/// instant is created is effectively constant (a few nanoseconds at most), which would make
+/// `cheap_jitter` return nearly the same value for every peer and every attempt, defeating the
+/// point of jitter; hashing `node_id` together with the attempt number instead gives a value that
+/// genuinely varies across peers and across retries of the same peer.
+fn jitter_seed(node_id: &str, attempt: u32) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325; // FNV-1a offset basis
+    for byte in node_id.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3); // FNV-1a prime
+    }
+    hash ^ u64::from(attempt).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// A small non-cryptographic mixing function used only to spread retry attempts across a window;
+/// it does not need to be unpredictable, only to avoid every member retrying in lockstep.
+fn cheap_jitter(seed: u64) -> u64 {
+    let mut x = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(1);
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x ^= x >> 27;
+    x
+}
+
+struct PendingPeer {
+    endpoint: String,
+    attempt: u32,
+    next_attempt_at: Instant,
+}
+
+/// A bounded, FIFO-evicted pool of established member connections, keyed by node id.
+pub struct ConnectionPool {
+    capacity: usize,
+    order: VecDeque<String>,
+    connections: HashMap<String, Box<dyn Connection>>,
+}
+
+impl ConnectionPool {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::new(),
+            connections: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, node_id: &str) -> Option<&dyn Connection> {
+        self.connections.get(node_id).map(AsRef::as_ref)
+    }
+
+    pub fn contains(&self, node_id: &str) -> bool {
+        self.connections.contains_key(node_id)
+    }
+
+    /// Insert a newly-established connection, evicting the oldest entry if the pool is full.
+    pub fn insert(&mut self, node_id: String, connection: Box<dyn Connection>) {
+        if !self.connections.contains_key(&node_id) {
+            if self.order.len() >= self.capacity {
+                if let Some(evicted) = self.order.pop_front() {
+                    self.connections.remove(&evicted);
+                }
+            }
+            self.order.push_back(node_id.clone());
+        }
+        self.connections.insert(node_id, connection);
+    }
+
+    pub fn remove(&mut self, node_id: &str) {
+        self.connections.remove(node_id);
+        self.order.retain(|id| id != node_id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.connections.is_empty()
+    }
+}
+
+/// Drives retries for circuit members that are not yet `PeerAuthorizationState::Authorized`, and
+/// owns the pool of connections to members that are.
+pub struct ReconnectManager {
+    peer_connector: PeerConnector,
+    policy: ReconnectPolicy,
+    pending: HashMap<String, PendingPeer>,
+    pool: ConnectionPool,
+}
+
+impl ReconnectManager {
+    pub fn new(peer_connector: PeerConnector, policy: ReconnectPolicy) -> Self {
+        Self {
+            peer_connector,
+            policy,
+            pending: HashMap::new(),
+            pool: ConnectionPool::new(16),
+        }
+    }
+
+    /// Queue `node_id` for retry against `endpoint`, starting at attempt zero. A no-op if the
+    /// node is already queued or already has a pooled connection.
+    pub fn queue(&mut self, node_id: &str, endpoint: &str) {
+        if self.pool.contains(node_id) || self.pending.contains_key(node_id) {
+            return;
+        }
+        self.pending.insert(
+            node_id.to_string(),
+            PendingPeer {
+                endpoint: endpoint.to_string(),
+                attempt: 0,
+                next_attempt_at: Instant::now(),
+            },
+        );
+    }
+
+    /// React to an authorization state change: a newly-authorized peer is no longer retried, and
+    /// a peer that drops out of authorization is queued for retry again.
+    pub fn handle_authorization_change(
+        &mut self,
+        node_id: &str,
+        endpoint: &str,
+        state: PeerAuthorizationState,
+    ) {
+        match state {
+            PeerAuthorizationState::Authorized => {
+                self.pending.remove(node_id);
+            }
+            _ => {
+                self.pool.remove(node_id);
+                self.queue(node_id, endpoint);
+            }
+        }
+    }
+
+    /// The node ids and endpoints of every peer whose next retry is due, so a caller can perform
+    /// the (blocking) connect attempts without holding whatever lock guards this manager.
+    pub fn due_peers(&self) -> Vec<(String, String)> {
+        let now = Instant::now();
+        self.pending
+            .iter()
+            .filter(|(_, peer)| peer.next_attempt_at <= now)
+            .map(|(node_id, peer)| (node_id.clone(), peer.endpoint.clone()))
+            .collect()
+    }
+
+    /// A handle to this manager's connector, cheap to clone, so a caller can dial a peer found
+    /// via `due_peers` without holding this manager's lock for the duration of the connect.
+    pub fn peer_connector(&self) -> PeerConnector {
+        self.peer_connector.clone()
+    }
+
+    /// Record the outcome of a connect attempt previously identified by `due_peers`. On success
+    /// the established connection is pooled for reuse; on failure the attempt counter advances
+    /// and, once `max_attempts` is exhausted, `node_id` is returned so the caller can fail any
+    /// proposal waiting on that peer.
+    pub fn complete_attempt(
+        &mut self,
+        node_id: &str,
+        result: Result<Box<dyn Connection>, ()>,
+    ) -> Option<String> {
+        match result {
+            Ok(connection) => {
+                self.pool.insert(node_id.to_string(), connection);
+                self.pending.remove(node_id);
+                None
+            }
+            Err(()) => {
+                let peer = self.pending.get_mut(node_id)?;
+                peer.attempt += 1;
+                if peer.attempt >= self.policy.max_attempts {
+                    self.pending.remove(node_id);
+                    Some(node_id.to_string())
+                } else {
+                    let delay =
+                        compute_delay(&self.policy, peer.attempt, jitter_seed(node_id, peer.attempt));
+                    peer.next_attempt_at = Instant::now() + delay;
+                    None
+                }
+            }
+        }
+    }
+
+    pub fn pool(&self) -> &ConnectionPool {
+        &self.pool
+    }
+
+    pub fn pool_mut(&mut self) -> &mut ConnectionPool {
+        &mut self.pool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jitter_seed_varies_by_peer_and_attempt() {
+        assert_ne!(jitter_seed("node-a", 0), jitter_seed("node-b", 0));
+        assert_ne!(jitter_seed("node-a", 0), jitter_seed("node-a", 1));
+    }
+
+    #[test]
+    fn test_compute_delay_caps_at_max_delay() {
+        let policy = ReconnectPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            max_attempts: 10,
+        };
+
+        // With no jitter ambiguity at attempt 0, the delay should be exactly base_delay, modulo
+        // the jitter term, which is bounded by half the capped delay.
+        let delay = compute_delay(&policy, 0, 42);
+        assert!(delay >= Duration::from_millis(100));
+        assert!(delay <= Duration::from_millis(150));
+
+        // At a high attempt count the exponential term should have saturated at max_delay.
+        let delay = compute_delay(&policy, 20, 42);
+        assert!(delay >= Duration::from_secs(1));
+        assert!(delay <= Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_connection_pool_evicts_oldest_when_full() {
+        struct NoopConnection;
+        impl Connection for NoopConnection {
+            fn send(&mut self, _message: &[u8]) -> Result<(), crate::transport::SendError> {
+                Ok(())
+            }
+            fn recv(&mut self) -> Result<Vec<u8>, crate::transport::RecvError> {
+                panic!("not used in this test")
+            }
+            fn remote_endpoint(&self) -> String {
+                "remote".into()
+            }
+            fn local_endpoint(&self) -> String {
+                "local".into()
+            }
+            fn disconnect(&mut self) -> Result<(), crate::transport::DisconnectError> {
+                Ok(())
+            }
+            fn evented(&self) -> &dyn mio::Evented {
+                unimplemented!("not used in this test")
+            }
+        }
+
+        let mut pool = ConnectionPool::new(2);
+        pool.insert("node-a".into(), Box::new(NoopConnection));
+        pool.insert("node-b".into(), Box::new(NoopConnection));
+        pool.insert("node-c".into(), Box::new(NoopConnection));
+
+        assert_eq!(pool.len(), 2);
+        assert!(!pool.contains("node-a"));
+        assert!(pool.contains("node-b"));
+        assert!(pool.contains("node-c"));
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Coordinated, drain-before-stop shutdown for the admin service.
+//!
+//! `Shutdown` tracks whether the service is still accepting new work. `AdminService` moves it to
+//! `Draining` at the start of `stop`, so the circuit proposal and websocket registration routes
+//! reject new work with `503` while any already-pending consensus proposals are given a chance
+//! to resolve. Votes are not rejected while `Draining`; the vote route returns `503` only once
+//! the state is `Stopped`.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const RUNNING: u8 = 0;
+const DRAINING: u8 = 1;
+const STOPPED: u8 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ServiceState {
+    Running,
+    Draining,
+    Stopped,
+}
+
+impl From<u8> for ServiceState {
+    fn from(value: u8) -> Self {
+        match value {
+            RUNNING => ServiceState::Running,
+            DRAINING => ServiceState::Draining,
+            _ => ServiceState::Stopped,
+        }
+    }
+}
+
+/// Shared shutdown state for the admin service: REST routes consult `is_accepting_new_work` (or,
+/// for votes, `is_resolving_pending_work`) and `stop` drives the state machine through `begin_draining` and `mark_stopped`.
+pub struct Shutdown {
+    state: AtomicU8,
+    drain_timeout: Duration,
+}
+
+impl Shutdown {
+    pub fn new(drain_timeout: Duration) -> Self {
+        Self {
+            state: AtomicU8::new(RUNNING),
+            drain_timeout,
+        }
+    }
+
+    pub fn state(&self) -> ServiceState {
+        ServiceState::from(self.state.load(Ordering::SeqCst))
+    }
+
+    pub fn is_accepting_new_work(&self) -> bool {
+        self.state() == ServiceState::Running
+    }
+
+    /// Whether routes that only resolve work already pending (e.g. casting a vote on an
+    /// existing proposal) should still be served. Unlike `is_accepting_new_work`, this stays
+    /// `true` through `Draining`: a vote can be what resolves an already-pending proposal, so
+    /// 503'ing them during drain could make `wait_for_drain` wait on a path it has itself closed.
+    /// Only `Stopped` rejects.
+    pub fn is_resolving_pending_work(&self) -> bool {
+        self.state() != ServiceState::Stopped
+    }
+
+    /// Move back to `Running`, so REST routes accept new work again. Called at the start of
+    /// `Service::start`, since a prior `stop` leaves the state at `Stopped`.
+    pub fn mark_running(&self) {
+        self.state.store(RUNNING, Ordering::SeqCst);
+    }
+
+    pub fn begin_draining(&self) {
+        self.state.store(DRAINING, Ordering::SeqCst);
+    }
+
+    pub fn mark_stopped(&self) {
+        self.state.store(STOPPED, Ordering::SeqCst);
+    }
+
+    /// Poll `pending_count` until it reports zero or `drain_timeout` elapses. Returns `true` if
+    /// draining completed cleanly, `false` if the timeout was hit first.
+    pub fn wait_for_drain<F: FnMut() -> usize>(&self, mut pending_count: F) -> bool {
+        let deadline = Instant::now() + self.drain_timeout;
+        loop {
+            if pending_count() == 0 {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+/// A cloneable handle that can be handed to a process signal handler (e.g. a `ctrlc` callback)
+/// so an operator's SIGINT requests an orderly quiesce rather than an abrupt drop. Triggering it
+/// does not stop the service by itself: whatever drives the service's lifecycle polls
+/// `is_triggered` and calls `Service::stop` in response.
+#[derive(Clone)]
+pub struct ShutdownTrigger {
+    requested: Arc<AtomicBool>,
+}
+
+impl ShutdownTrigger {
+    pub fn new() -> Self {
+        Self {
+            requested: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn trigger(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for ShutdownTrigger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_begin_draining_rejects_new_work_until_running_again() {
+        let shutdown = Shutdown::new(Duration::from_millis(100));
+        assert!(shutdown.is_accepting_new_work());
+
+        shutdown.begin_draining();
+        assert!(!shutdown.is_accepting_new_work());
+        assert_eq!(shutdown.state(), ServiceState::Draining);
+
+        shutdown.mark_stopped();
+        assert_eq!(shutdown.state(), ServiceState::Stopped);
+
+        shutdown.mark_running();
+        assert!(shutdown.is_accepting_new_work());
+        assert_eq!(shutdown.state(), ServiceState::Running);
+    }
+
+    #[test]
+    fn test_is_resolving_pending_work_stays_true_while_draining() {
+        let shutdown = Shutdown::new(Duration::from_millis(100));
+        assert!(shutdown.is_resolving_pending_work());
+
+        shutdown.begin_draining();
+        assert!(!shutdown.is_accepting_new_work());
+        assert!(shutdown.is_resolving_pending_work());
+
+        shutdown.mark_stopped();
+        assert!(!shutdown.is_resolving_pending_work());
+    }
+
+    #[test]
+    fn test_wait_for_drain_returns_true_once_pending_reaches_zero() {
+        let shutdown = Shutdown::new(Duration::from_secs(1));
+        let mut remaining = 2;
+
+        let drained = shutdown.wait_for_drain(|| {
+            if remaining > 0 {
+                remaining -= 1;
+            }
+            remaining
+        });
+
+        assert!(drained);
+    }
+
+    #[test]
+    fn test_wait_for_drain_times_out() {
+        let shutdown = Shutdown::new(Duration::from_millis(50));
+        let drained = shutdown.wait_for_drain(|| 1);
+        assert!(!drained);
+    }
+
+    #[test]
+    fn test_shutdown_trigger_round_trip() {
+        let trigger = ShutdownTrigger::new();
+        assert!(!trigger.is_triggered());
+        trigger.trigger();
+        assert!(trigger.is_triggered());
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detached-signature support for circuit proposals and votes.
+//!
+//! Each node holds an Ed25519 keypair. Outgoing `CircuitManagementPayload`s (create requests and
+//! votes) are signed over their deterministic protobuf encoding, and `verify` checks an incoming
+//! payload's signature against the purported signer's registered public key before the admin
+//! service will act on it.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::RwLock;
+
+use openssl::error::ErrorStack;
+use openssl::pkey::{Id, PKey, Private, Public};
+use openssl::sign::{Signer, Verifier};
+
+/// An Ed25519 keypair used to sign outgoing circuit management payloads.
+pub struct SigningKeyPair {
+    private_key: PKey<Private>,
+    public_key: PKey<Public>,
+}
+
+impl SigningKeyPair {
+    /// Generate a new random Ed25519 keypair.
+    pub fn generate() -> Result<Self, SigningError> {
+        let private_key = PKey::generate_ed25519().map_err(SigningError::from)?;
+        let public_key_bytes = private_key
+            .raw_public_key()
+            .map_err(SigningError::from)?;
+        let public_key =
+            PKey::public_key_from_raw_bytes(&public_key_bytes, Id::ED25519).map_err(SigningError::from)?;
+
+        Ok(Self {
+            private_key,
+            public_key,
+        })
+    }
+
+    /// The raw bytes of this keypair's public key, suitable for registering with peers.
+    pub fn public_key_bytes(&self) -> Result<Vec<u8>, SigningError> {
+        self.public_key
+            .raw_public_key()
+            .map_err(SigningError::from)
+    }
+
+    /// Sign `message` with the private half of this keypair, returning a detached signature.
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SigningError> {
+        let mut signer =
+            Signer::new_without_digest(&self.private_key).map_err(SigningError::from)?;
+        signer.sign_oneshot_to_vec(message).map_err(SigningError::from)
+    }
+}
+
+/// Verify a detached Ed25519 `signature` over `message`, using the raw public key bytes of the
+/// purported signer.
+pub fn verify(public_key_bytes: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, SigningError> {
+    let public_key =
+        PKey::public_key_from_raw_bytes(public_key_bytes, Id::ED25519).map_err(SigningError::from)?;
+    let mut verifier = Verifier::new_without_digest(&public_key).map_err(SigningError::from)?;
+    verifier
+        .verify_oneshot(signature, message)
+        .map_err(SigningError::from)
+}
+
+/// A registry mapping node ids to the public keys that node has announced, used to verify
+/// signatures on incoming proposals and votes.
+#[derive(Default)]
+pub struct KeyRegistry {
+    keys_by_node: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl KeyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) the public key a node signs its payloads with.
+    pub fn register_key(&self, node_id: &str, public_key: Vec<u8>) {
+        if let Ok(mut keys) = self.keys_by_node.write() {
+            keys.insert(node_id.to_string(), public_key);
+        }
+    }
+
+    /// Look up the registered public key for a node id, if any.
+    pub fn public_key_for_node(&self, node_id: &str) -> Option<Vec<u8>> {
+        self.keys_by_node
+            .read()
+            .ok()
+            .and_then(|keys| keys.get(node_id).cloned())
+    }
+}
+
+#[derive(Debug)]
+pub enum SigningError {
+    OpenSsl(ErrorStack),
+}
+
+impl Error for SigningError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SigningError::OpenSsl(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for SigningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SigningError::OpenSsl(err) => write!(f, "signing operation failed: {}", err),
+        }
+    }
+}
+
+impl From<ErrorStack> for SigningError {
+    fn from(err: ErrorStack) -> Self {
+        SigningError::OpenSsl(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let key_pair = SigningKeyPair::generate().expect("failed to generate keypair");
+        let message = b"a deterministic protobuf encoding";
+
+        let signature = key_pair.sign(message).expect("failed to sign message");
+        let public_key = key_pair
+            .public_key_bytes()
+            .expect("failed to read public key");
+
+        assert!(verify(&public_key, message, &signature).expect("verification failed"));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_message() {
+        let key_pair = SigningKeyPair::generate().expect("failed to generate keypair");
+        let signature = key_pair.sign(b"original message").expect("failed to sign message");
+        let public_key = key_pair
+            .public_key_bytes()
+            .expect("failed to read public key");
+
+        assert!(!verify(&public_key, b"tampered message", &signature).expect("verification failed"));
+    }
+}